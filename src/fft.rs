@@ -0,0 +1,157 @@
+// Copyright (c) 2020 Apple Inc.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Radix-2 number-theoretic transform (NTT), generic over any field
+//! implementing [`FieldElement`].
+//!
+//! This lets polynomial evaluation and interpolation run in `O(n log n)`
+//! instead of the `O(n^2)` of the naive approach, using the field's
+//! power-of-two subgroup of roots of unity (see `FieldElement::GENERATOR`
+//! and `FieldElement::N_ROOTS`).
+
+use crate::field::FieldElement;
+
+/// Runs an in-place radix-2 Cooley-Tukey NTT over `data`.
+///
+/// `data.len()` must be a power of two `n = 2^k` with `n <= F::N_ROOTS`;
+/// panics otherwise, since the field only has roots of unity up to order
+/// `F::N_ROOTS`.
+///
+/// When `inverse` is `false` this evaluates the polynomial with
+/// coefficients `data` at the `n`-th roots of unity. When `true`, it runs
+/// the inverse transform, recovering coefficients from evaluations.
+pub fn fft<F: FieldElement>(data: &mut [F], inverse: bool) {
+    let n = data.len();
+    assert!(
+        n.is_power_of_two(),
+        "fft: data length must be a power of two"
+    );
+    assert!(
+        n as u128 <= F::N_ROOTS,
+        "fft: data length must not exceed N_ROOTS"
+    );
+
+    if n <= 1 {
+        return;
+    }
+
+    let mut omega =
+        F::from_u64(F::GENERATOR as u64).pow(F::from_u64((F::N_ROOTS / n as u128) as u64));
+    if inverse {
+        omega = omega.inv();
+    }
+
+    bit_reverse_permute(data);
+
+    let mut len = 2;
+    while len <= n {
+        let w_len = omega.pow(F::from_u64((n / len) as u64));
+        let mut i = 0;
+        while i < n {
+            let mut w = F::one();
+            for j in 0..len / 2 {
+                let u = data[i + j];
+                let v = data[i + j + len / 2] * w;
+                data[i + j] = u + v;
+                data[i + j + len / 2] = u - v;
+                w *= w_len;
+            }
+            i += len;
+        }
+        len *= 2;
+    }
+
+    if inverse {
+        let n_inv = F::from_u64(n as u64).inv();
+        for x in data.iter_mut() {
+            *x *= n_inv;
+        }
+    }
+}
+
+/// Reorders `data` in place so that `data[i]` and `data[reverse_bits(i)]`
+/// are swapped, where `reverse_bits` reverses the `log2(data.len())` least
+/// significant bits of `i`.
+fn bit_reverse_permute<F: FieldElement>(data: &mut [F]) {
+    let n = data.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = ((i as u32).reverse_bits() >> (32 - bits)) as usize;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+}
+
+#[test]
+fn test_fft_round_trip() {
+    use crate::finite_field::Field;
+    use rand::prelude::*;
+
+    let mut rng = thread_rng();
+    for size in [1, 2, 4, 8, 16, 128] {
+        let original: Vec<Field> = (0..size).map(|_| Field::from(rng.gen::<u32>())).collect();
+        let mut data = original.clone();
+        fft(&mut data, false);
+        fft(&mut data, true);
+        assert_eq!(data, original);
+    }
+}
+
+#[test]
+fn test_fft_matches_naive_dft() {
+    use crate::finite_field::{Field, GENERATOR, N_ROOTS};
+    use rand::prelude::*;
+
+    fn naive_dft(data: &[Field], inverse: bool) -> Vec<Field> {
+        let n = data.len();
+        let mut omega = Field::from(GENERATOR).pow(Field::from(N_ROOTS / n as u32));
+        if inverse {
+            omega = omega.inv();
+        }
+        let mut out = Vec::with_capacity(n);
+        for k in 0..n {
+            let mut s = Field::from(0);
+            let mut w = Field::from(1);
+            let step = omega.pow(Field::from(k as u32));
+            for &d in data.iter() {
+                s += d * w;
+                w *= step;
+            }
+            out.push(s);
+        }
+        if inverse {
+            let n_inv = Field::from(n as u32).inv();
+            for x in out.iter_mut() {
+                *x *= n_inv;
+            }
+        }
+        out
+    }
+
+    let mut rng = thread_rng();
+    for size in [4, 8, 16] {
+        let original: Vec<Field> = (0..size).map(|_| Field::from(rng.gen::<u32>())).collect();
+        let mut data = original.clone();
+        fft(&mut data, false);
+        assert_eq!(data, naive_dft(&original, false));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_fft_rejects_non_power_of_two() {
+    use crate::finite_field::Field;
+
+    let mut data = vec![Field::from(1); 3];
+    fft(&mut data, false);
+}
+
+#[test]
+#[should_panic]
+fn test_fft_rejects_too_large() {
+    use crate::finite_field::Field;
+
+    let mut data = vec![Field::from(1); 1 << 21];
+    fft(&mut data, false);
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2020 Apple Inc.
+// SPDX-License-Identifier: MPL-2.0
+
+//! A common interface for the finite fields used throughout this crate,
+//! modeled on the `PrimeField`/`ff` abstraction used by other finite-field
+//! crates. [`crate::finite_field::Field`] is the original 32-bit
+//! implementation; [`crate::field64::Field64`] is a larger 64-bit field for
+//! aggregations whose values overflow the 32-bit modulus's dynamic range.
+
+/// Errors that can occur when reading the canonical byte encoding of a
+/// field element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FiniteFieldError {
+    /// The input was not exactly `FieldElement::ENCODED_SIZE` bytes long
+    /// (or, for a vector of elements, not a multiple of it).
+    InputSizeMismatch,
+    /// The encoded value was `>= MODULUS`, so it does not canonically
+    /// represent any field element.
+    ModulusOverflow,
+}
+
+impl std::fmt::Display for FiniteFieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FiniteFieldError::InputSizeMismatch => {
+                write!(f, "input sized different than field size")
+            }
+            FiniteFieldError::ModulusOverflow => write!(f, "value overflows field modulus"),
+        }
+    }
+}
+
+impl std::error::Error for FiniteFieldError {}
+
+/// Common arithmetic for a finite prime field, so that aggregation and
+/// proof code can be generic over the field size a deployment needs.
+pub trait FieldElement:
+    Sized
+    + Copy
+    + Clone
+    + PartialEq
+    + Eq
+    + std::fmt::Debug
+    + std::fmt::Display
+    + std::ops::Add<Output = Self>
+    + std::ops::AddAssign
+    + std::ops::Sub<Output = Self>
+    + std::ops::SubAssign
+    + std::ops::Mul<Output = Self>
+    + std::ops::MulAssign
+    + std::ops::Div<Output = Self>
+    + std::ops::DivAssign
+{
+    /// The field's prime modulus.
+    const MODULUS: u128;
+    /// Generator of the field's multiplicative subgroup of order
+    /// `Self::N_ROOTS`, used for NTT-style evaluation.
+    const GENERATOR: u128;
+    /// Order of the largest power-of-two subgroup of the multiplicative
+    /// group, i.e. the largest NTT this field supports.
+    const N_ROOTS: u128;
+    /// Length in bytes of this field's canonical encoding.
+    const ENCODED_SIZE: usize;
+
+    /// The additive identity.
+    fn zero() -> Self;
+    /// The multiplicative identity.
+    fn one() -> Self;
+    /// Modular exponentiation, `self^exp`.
+    fn pow(self, exp: Self) -> Self;
+    /// Modular inverse. The inverse of zero is defined as zero.
+    fn inv(self) -> Self;
+    /// Converts a plain integer smaller than the field's word size into a
+    /// field element. Used to build small constants (array lengths,
+    /// exponents) generically, without reaching for a field-specific
+    /// `From` impl.
+    fn from_u64(value: u64) -> Self;
+    /// Reads a field element from its canonical little-endian encoding,
+    /// rejecting any value `>= Self::MODULUS`.
+    fn try_from_bytes(bytes: &[u8]) -> Result<Self, FiniteFieldError>;
+    /// Writes this field element to its canonical little-endian encoding.
+    fn into_bytes(self) -> Vec<u8>;
+}
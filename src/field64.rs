@@ -0,0 +1,292 @@
+// Copyright (c) 2020 Apple Inc.
+// SPDX-License-Identifier: MPL-2.0
+
+//! A second, 64-bit finite field, for aggregations whose values overflow
+//! the dynamic range of the 32-bit [`crate::finite_field::Field`].
+
+use crate::field::{FieldElement, FiniteFieldError};
+
+/// Newtype wrapper over u64
+///
+/// Implements the arithmetic over a 64-bit finite prime field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Field64(u64);
+
+/// Modulus for the field, a FFT friendly prime: 2^64 - 2^32 + 1 (the
+/// "Goldilocks" prime), chosen for its large power-of-two 2-Sylow subgroup.
+pub const MODULUS64: u64 = 0xFFFF_FFFF_0000_0001;
+/// Generator of the multiplicative subgroup of order `N_ROOTS64`.
+pub(crate) const GENERATOR64: u64 = 1_753_635_133_440_165_772;
+/// Order of the 2-Sylow subgroup: `MODULUS64 - 1 == N_ROOTS64 * (2^32 - 1)`.
+pub(crate) const N_ROOTS64: u64 = 1 << 32;
+
+impl std::ops::Add for Field64 {
+    type Output = Field64;
+
+    fn add(self, rhs: Self) -> Self {
+        self - Field64(MODULUS64 - rhs.0)
+    }
+}
+
+impl std::ops::AddAssign for Field64 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::Sub for Field64 {
+    type Output = Field64;
+
+    fn sub(self, rhs: Self) -> Self {
+        let l = self.0;
+        let r = rhs.0;
+
+        if l >= r {
+            Field64(l - r)
+        } else {
+            Field64(MODULUS64 - r + l)
+        }
+    }
+}
+
+impl std::ops::SubAssign for Field64 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl std::ops::Mul for Field64 {
+    type Output = Field64;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self {
+        let l = self.0 as u128;
+        let r = rhs.0 as u128;
+        Field64(((l * r) % (MODULUS64 as u128)) as u64)
+    }
+}
+
+impl std::ops::MulAssign for Field64 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::ops::Div for Field64 {
+    type Output = Field64;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl std::ops::DivAssign for Field64 {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Field64 {
+    /// The additive identity.
+    pub fn zero() -> Self {
+        Field64::from(0)
+    }
+
+    /// The multiplicative identity.
+    pub fn one() -> Self {
+        Field64::from(1)
+    }
+
+    /// Modular exponentation
+    pub fn pow(self, exp: Self) -> Self {
+        // repeated squaring
+        let mut base = self;
+        let mut exp = exp.0;
+        let mut result: Field64 = Field64::from(1);
+        while exp > 0 {
+            while (exp & 1) == 0 {
+                exp /= 2;
+                base *= base;
+            }
+            exp -= 1;
+            result *= base;
+        }
+        result
+    }
+
+    /// Modular inverse
+    ///
+    /// Note: inverse of 0 is defined as 0.
+    pub fn inv(self) -> Self {
+        // extended Euclidean
+        let mut x1: i128 = 1;
+        let mut a1: u64 = self.0;
+        let mut x0: i128 = 0;
+        let mut a2: u64 = MODULUS64;
+        let mut q: u64 = 0;
+
+        while a2 != 0 {
+            let x2 = x0 - (q as i128) * x1;
+            x0 = x1;
+            let a0 = a1;
+            x1 = x2;
+            a1 = a2;
+            q = a0 / a1;
+            a2 = a0 - q * a1;
+        }
+        if x1 < 0 {
+            Field64(((x1 + MODULUS64 as i128) as u64) % MODULUS64)
+        } else {
+            Field64((x1 as u64) % MODULUS64)
+        }
+    }
+
+    /// Reads a field element from its canonical 8-byte little-endian
+    /// encoding, rejecting any value `>= MODULUS64`.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, FiniteFieldError> {
+        if bytes.len() != 8 {
+            return Err(FiniteFieldError::InputSizeMismatch);
+        }
+        let mut buf = [0; 8];
+        buf.copy_from_slice(bytes);
+        let x = u64::from_le_bytes(buf);
+        if x >= MODULUS64 {
+            return Err(FiniteFieldError::ModulusOverflow);
+        }
+        Ok(Field64(x))
+    }
+
+    /// Writes this field element to its canonical 8-byte little-endian
+    /// encoding.
+    pub fn into_bytes(self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+}
+
+impl From<u64> for Field64 {
+    fn from(x: u64) -> Self {
+        Field64(x % MODULUS64)
+    }
+}
+
+impl From<Field64> for u64 {
+    fn from(x: Field64) -> Self {
+        x.0
+    }
+}
+
+impl PartialEq<u64> for Field64 {
+    fn eq(&self, rhs: &u64) -> bool {
+        self.0 == *rhs
+    }
+}
+
+impl std::fmt::Display for Field64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FieldElement for Field64 {
+    const MODULUS: u128 = MODULUS64 as u128;
+    const GENERATOR: u128 = GENERATOR64 as u128;
+    const N_ROOTS: u128 = N_ROOTS64 as u128;
+    const ENCODED_SIZE: usize = 8;
+
+    fn zero() -> Self {
+        Field64::zero()
+    }
+
+    fn one() -> Self {
+        Field64::one()
+    }
+
+    fn pow(self, exp: Self) -> Self {
+        Field64::pow(self, exp)
+    }
+
+    fn inv(self) -> Self {
+        Field64::inv(self)
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Field64::from(value)
+    }
+
+    fn try_from_bytes(bytes: &[u8]) -> Result<Self, FiniteFieldError> {
+        Field64::try_from_bytes(bytes)
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        Field64::into_bytes(self).to_vec()
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    use rand::prelude::*;
+
+    // add
+    assert_eq!(Field64::from(MODULUS64 - 1) + Field64::from(1), 0);
+    assert_eq!(Field64::from(1) + Field64::from(1), 2);
+    assert_eq!(Field64::from(2) + Field64::from(MODULUS64), 2);
+
+    // sub
+    assert_eq!(Field64::from(0) - Field64::from(1), MODULUS64 - 1);
+    assert_eq!(Field64::from(15) - Field64::from(3), 12);
+
+    // add + sub
+    for _ in 0..100 {
+        let f = Field64::from(random::<u64>());
+        let g = Field64::from(random::<u64>());
+        assert_eq!(f + g - f - g, 0);
+        assert_eq!(f + g - g, f);
+        assert_eq!(f + g - f, g);
+    }
+
+    // mul + div
+    assert_eq!(Field64::from(35) * Field64::from(123), 4305);
+    assert_eq!(Field64::from(35) / Field64::from(5), 7);
+    assert_eq!(Field64::from(35) / Field64::from(0), 0);
+    assert_eq!(Field64::from(0).inv(), 0);
+
+    let uniform = rand::distributions::Uniform::from(1..MODULUS64);
+    let mut rng = thread_rng();
+    for _ in 0..100 {
+        let f = Field64::from(uniform.sample(&mut rng));
+        assert_eq!(f * f.inv(), 1);
+        assert_eq!(f.inv() * f, 1);
+    }
+
+    // pow
+    assert_eq!(Field64::from(2).pow(3.into()), 8);
+    assert_eq!(Field64::from(3).pow(9.into()), 19683);
+    assert_eq!(Field64::from(0).pow(123.into()), 0);
+}
+
+#[test]
+fn test_byte_codec() {
+    let f = Field64::from(123456789);
+    assert_eq!(Field64::try_from_bytes(&f.into_bytes()).unwrap(), f);
+
+    assert_eq!(
+        Field64::try_from_bytes(&[1, 2, 3]),
+        Err(FiniteFieldError::InputSizeMismatch)
+    );
+    assert_eq!(
+        Field64::try_from_bytes(&MODULUS64.to_le_bytes()),
+        Err(FiniteFieldError::ModulusOverflow)
+    );
+}
+
+#[test]
+fn test_fft_generic_over_field64() {
+    use crate::fft::fft;
+
+    let original: Vec<Field64> = (0..16u64).map(Field64::from).collect();
+    let mut data = original.clone();
+    fft(&mut data, false);
+    fft(&mut data, true);
+    assert_eq!(data, original);
+}
@@ -3,14 +3,51 @@
 
 //! Finite field arithmetic over a prime field using a 32bit prime.
 
-use serde::{Deserialize, Serialize};
+use crate::field::{FieldElement, FiniteFieldError};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Newtype wrapper over u32
 ///
-/// Implements the arithmetic over the finite prime field
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+/// Implements the arithmetic over the finite prime field. Values are stored
+/// internally in Montgomery form (`x * R mod MODULUS`) so that multiplication
+/// can be carried out with REDC instead of a 64-bit division. `Ord` and
+/// `Serialize`/`Deserialize` are implemented by hand below rather than
+/// derived, since deriving them would order/encode elements by their
+/// internal Montgomery residue instead of by the canonical field value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub struct Field(u32);
 
+impl Serialize for Field {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.into_bytes().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = <[u8; 4]>::deserialize(deserializer)?;
+        Field::try_from_bytes(&bytes).map_err(de::Error::custom)
+    }
+}
+
+impl PartialOrd for Field {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Field {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        u32::from(*self).cmp(&u32::from(*other))
+    }
+}
+
 /// Modulus for the field, a FFT friendly prime: 2^32 - 2^20 + 1
 pub const MODULUS: u32 = 4293918721;
 /// Generator for the multiplicative subgroup
@@ -18,6 +55,42 @@ pub(crate) const GENERATOR: u32 = 3925978153;
 /// Number of primitive roots
 pub(crate) const N_ROOTS: u32 = 1 << 20; // number of primitive roots
 
+// Montgomery arithmetic constants for MODULUS, with R = 2^32.
+//
+// MONT_R = R mod MODULUS, i.e. the Montgomery form of 1.
+const MONT_R: u32 = ((1u64 << 32) % MODULUS as u64) as u32;
+// MONT_R2 = R^2 mod MODULUS, used to move a plain value into Montgomery form.
+const MONT_R2: u32 = ((MONT_R as u64 * MONT_R as u64) % MODULUS as u64) as u32;
+// MONT_INV = -MODULUS^{-1} mod 2^32, used by REDC.
+const MONT_INV: u32 = mont_inv(MODULUS);
+
+/// Computes `-modulus^{-1} mod 2^32` via Newton's method, starting from the
+/// (always correct) 1-bit inverse of an odd number and doubling the number
+/// of correct bits on each iteration.
+const fn mont_inv(modulus: u32) -> u32 {
+    let mut inv: u32 = 1;
+    let mut i = 0;
+    while i < 5 {
+        inv = inv.wrapping_mul(2u32.wrapping_sub(modulus.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+/// Montgomery reduction (REDC): given `t`, returns `t * R^{-1} mod MODULUS`.
+///
+/// The addition below is carried out in `u128` because `MODULUS` is so close
+/// to `2^32` that `t + m * MODULUS` can overflow `u64` for the largest inputs.
+fn redc(t: u64) -> u32 {
+    let m = (t as u32).wrapping_mul(MONT_INV);
+    let t = ((t as u128) + (m as u128) * (MODULUS as u128)) >> 32;
+    if t >= MODULUS as u128 {
+        (t - MODULUS as u128) as u32
+    } else {
+        t as u32
+    }
+}
+
 impl std::ops::Add for Field {
     type Output = Field;
 
@@ -60,8 +133,7 @@ impl std::ops::Mul for Field {
     fn mul(self, rhs: Self) -> Self {
         let l = self.0 as u64;
         let r = rhs.0 as u64;
-        let mul = l * r;
-        Field((mul % (MODULUS as u64)) as u32)
+        Field(redc(l * r))
     }
 }
 
@@ -87,12 +159,22 @@ impl std::ops::DivAssign for Field {
 }
 
 impl Field {
+    /// The additive identity.
+    pub fn zero() -> Self {
+        Field::from(0)
+    }
+
+    /// The multiplicative identity.
+    pub fn one() -> Self {
+        Field::from(1)
+    }
+
     /// Modular exponentation
     pub fn pow(self, exp: Self) -> Self {
         // repeated squaring
         let mut base = self;
-        let mut exp = exp.0;
-        let mut result: Field = Field(1);
+        let mut exp = u32::from(exp);
+        let mut result: Field = Field::from(1u32);
         while exp > 0 {
             while (exp & 1) == 0 {
                 exp /= 2;
@@ -108,9 +190,9 @@ impl Field {
     ///
     /// Note: inverse of 0 is defined as 0.
     pub fn inv(self) -> Self {
-        // extended Euclidean
+        // extended Euclidean, carried out on the plain (non-Montgomery) value
         let mut x1: i32 = 1;
-        let mut a1: u32 = self.0;
+        let mut a1: u32 = u32::from(self);
         let mut x0: i32 = 0;
         let mut a2: u32 = MODULUS;
         let mut q: u32 = 0;
@@ -124,36 +206,195 @@ impl Field {
             q = a0 / a1;
             a2 = a0 - q * a1;
         }
-        if x1 < 0 {
+        let inv = if x1 < 0 {
             let (r, _) = MODULUS.overflowing_add(x1 as u32);
-            Field(r)
+            r
         } else {
-            Field(x1 as u32)
+            x1 as u32
+        };
+        Field::from(inv)
+    }
+
+    /// Computes a square root of `self` using Tonelli-Shanks.
+    ///
+    /// Returns `Some(root)` if `self` is a quadratic residue modulo
+    /// `MODULUS`, and `None` otherwise.
+    pub fn sqrt(self) -> Option<Field> {
+        if self == Field::from(0) {
+            return Some(Field::from(0));
+        }
+
+        // MODULUS - 1 = 2^20 * Q with Q odd, so Tonelli-Shanks needs only
+        // `m` (the remaining power of two) to start at 20. GENERATOR
+        // generates the whole 2^20-order 2-Sylow subgroup, so it is a
+        // quadratic non-residue.
+        const Q: u32 = (MODULUS - 1) / N_ROOTS;
+
+        let z = Field::from(GENERATOR);
+        let mut c = z.pow(Field::from(Q));
+        let mut r = self.pow(Field::from(Q.div_ceil(2)));
+        let mut t = self.pow(Field::from(Q));
+        let mut m = 20u32;
+
+        while t != Field::from(1) {
+            // find the least i in (0, m) with t^(2^i) == 1
+            let mut i = 0;
+            let mut tt = t;
+            while tt != Field::from(1) {
+                if i + 1 >= m {
+                    // self was not a quadratic residue after all
+                    return None;
+                }
+                tt *= tt;
+                i += 1;
+            }
+
+            let b = c.pow(Field::from(1u32 << (m - i - 1)));
+            r *= b;
+            c = b * b;
+            t *= c;
+            m = i;
+        }
+
+        if r * r == self {
+            Some(r)
+        } else {
+            None
+        }
+    }
+
+    /// Reads a field element from its canonical 4-byte little-endian
+    /// encoding.
+    ///
+    /// Unlike `From<u32>`, this rejects any encoded value `>= MODULUS`
+    /// instead of silently reducing it, so that shares and proofs have a
+    /// single, non-malleable wire representation.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, FiniteFieldError> {
+        if bytes.len() != 4 {
+            return Err(FiniteFieldError::InputSizeMismatch);
+        }
+        let mut buf = [0; 4];
+        buf.copy_from_slice(bytes);
+        let x = u32::from_le_bytes(buf);
+        if x >= MODULUS {
+            return Err(FiniteFieldError::ModulusOverflow);
+        }
+        Ok(Field::from(x))
+    }
+
+    /// Writes this field element to its canonical 4-byte little-endian
+    /// encoding.
+    pub fn into_bytes(self) -> [u8; 4] {
+        u32::from(self).to_le_bytes()
+    }
+}
+
+/// Deserializes `bytes` into a vector of field elements. `bytes` must have
+/// a length that is a multiple of 4, and each 4-byte chunk must be a
+/// canonical encoding per [`Field::try_from_bytes`].
+pub fn deserialize_field_vec(bytes: &[u8]) -> Result<Vec<Field>, FiniteFieldError> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(FiniteFieldError::InputSizeMismatch);
+    }
+    bytes.chunks_exact(4).map(Field::try_from_bytes).collect()
+}
+
+/// Serializes `values` into their canonical byte encoding, concatenated in
+/// order. The inverse of [`deserialize_field_vec`].
+pub fn serialize_field_vec(values: &[Field]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(values.len() * 4);
+    for value in values {
+        bytes.extend_from_slice(&value.into_bytes());
+    }
+    bytes
+}
+
+/// Inverts every element of `values` in place using Montgomery's batch
+/// inversion trick: a single `inv()` call plus `O(n)` multiplications,
+/// instead of `n` calls to `inv()`.
+///
+/// As with [`Field::inv`], the inverse of `0` is `0`.
+pub fn batch_inv(values: &mut [Field]) {
+    // prefixes[i] holds the product of all non-zero elements in values[..i]
+    // (excluding values[i] itself), skipping any zero elements so the
+    // running product never hits zero.
+    let mut prefix = Field::from(1);
+    let mut prefixes = Vec::with_capacity(values.len());
+    for &v in values.iter() {
+        prefixes.push(prefix);
+        if v != Field::from(0) {
+            prefix *= v;
         }
     }
+
+    let mut acc = prefix.inv();
+    for i in (0..values.len()).rev() {
+        let v = values[i];
+        if v == Field::from(0) {
+            continue;
+        }
+        values[i] = acc * prefixes[i];
+        acc *= v;
+    }
+}
+
+impl FieldElement for Field {
+    const MODULUS: u128 = MODULUS as u128;
+    const GENERATOR: u128 = GENERATOR as u128;
+    const N_ROOTS: u128 = N_ROOTS as u128;
+    const ENCODED_SIZE: usize = 4;
+
+    fn zero() -> Self {
+        Field::zero()
+    }
+
+    fn one() -> Self {
+        Field::one()
+    }
+
+    fn pow(self, exp: Self) -> Self {
+        Field::pow(self, exp)
+    }
+
+    fn inv(self) -> Self {
+        Field::inv(self)
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Field::from(value as u32)
+    }
+
+    fn try_from_bytes(bytes: &[u8]) -> Result<Self, FiniteFieldError> {
+        Field::try_from_bytes(bytes)
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        Field::into_bytes(self).to_vec()
+    }
 }
 
 impl From<u32> for Field {
     fn from(x: u32) -> Self {
-        Field(x % MODULUS)
+        let x = x % MODULUS;
+        Field(redc((x as u64) * (MONT_R2 as u64)))
     }
 }
 
 impl From<Field> for u32 {
     fn from(x: Field) -> Self {
-        x.0
+        redc(x.0 as u64)
     }
 }
 
 impl PartialEq<u32> for Field {
     fn eq(&self, rhs: &u32) -> bool {
-        self.0 == *rhs
+        u32::from(*self) == *rhs
     }
 }
 
 impl std::fmt::Display for Field {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", u32::from(*self))
     }
 }
 
@@ -161,20 +402,20 @@ impl std::fmt::Display for Field {
 fn test_arithmetic() {
     use rand::prelude::*;
     // add
-    assert_eq!(Field(MODULUS - 1) + Field(1), 0);
-    assert_eq!(Field(MODULUS - 2) + Field(2), 0);
-    assert_eq!(Field(MODULUS - 2) + Field(3), 1);
-    assert_eq!(Field(1) + Field(1), 2);
-    assert_eq!(Field(2) + Field(MODULUS), 2);
-    assert_eq!(Field(3) + Field(MODULUS - 1), 2);
+    assert_eq!(Field::from(MODULUS - 1) + Field::from(1), 0);
+    assert_eq!(Field::from(MODULUS - 2) + Field::from(2), 0);
+    assert_eq!(Field::from(MODULUS - 2) + Field::from(3), 1);
+    assert_eq!(Field::from(1) + Field::from(1), 2);
+    assert_eq!(Field::from(2) + Field::from(MODULUS), 2);
+    assert_eq!(Field::from(3) + Field::from(MODULUS - 1), 2);
 
     // sub
-    assert_eq!(Field(0) - Field(1), MODULUS - 1);
-    assert_eq!(Field(1) - Field(2), MODULUS - 1);
-    assert_eq!(Field(15) - Field(3), 12);
-    assert_eq!(Field(1) - Field(1), 0);
-    assert_eq!(Field(2) - Field(MODULUS), 2);
-    assert_eq!(Field(3) - Field(MODULUS - 1), 4);
+    assert_eq!(Field::from(0) - Field::from(1), MODULUS - 1);
+    assert_eq!(Field::from(1) - Field::from(2), MODULUS - 1);
+    assert_eq!(Field::from(15) - Field::from(3), 12);
+    assert_eq!(Field::from(1) - Field::from(1), 0);
+    assert_eq!(Field::from(2) - Field::from(MODULUS), 2);
+    assert_eq!(Field::from(3) - Field::from(MODULUS - 1), 4);
 
     // add + sub
     for _ in 0..100 {
@@ -186,34 +427,119 @@ fn test_arithmetic() {
     }
 
     // mul
-    assert_eq!(Field(35) * Field(123), 4305);
-    assert_eq!(Field(1) * Field(MODULUS), 0);
-    assert_eq!(Field(0) * Field(123), 0);
-    assert_eq!(Field(123) * Field(0), 0);
-    assert_eq!(Field(123123123) * Field(123123123), 1237630077);
+    assert_eq!(Field::from(35) * Field::from(123), 4305);
+    assert_eq!(Field::from(1) * Field::from(MODULUS), 0);
+    assert_eq!(Field::from(0) * Field::from(123), 0);
+    assert_eq!(Field::from(123) * Field::from(0), 0);
+    assert_eq!(Field::from(123123123) * Field::from(123123123), 1237630077);
 
     // div
-    assert_eq!(Field(35) / Field(5), 7);
-    assert_eq!(Field(35) / Field(0), 0);
-    assert_eq!(Field(0) / Field(5), 0);
-    assert_eq!(Field(1237630077) / Field(123123123), 123123123);
+    assert_eq!(Field::from(35) / Field::from(5), 7);
+    assert_eq!(Field::from(35) / Field::from(0), 0);
+    assert_eq!(Field::from(0) / Field::from(5), 0);
+    assert_eq!(Field::from(1237630077) / Field::from(123123123), 123123123);
 
-    assert_eq!(Field(0).inv(), 0);
+    assert_eq!(Field::from(0).inv(), 0);
 
     // mul and div
     let uniform = rand::distributions::Uniform::from(1..MODULUS);
     let mut rng = thread_rng();
     for _ in 0..100 {
         // non-zero element
-        let f = Field(uniform.sample(&mut rng));
+        let f = Field::from(uniform.sample(&mut rng));
         assert_eq!(f * f.inv(), 1);
         assert_eq!(f.inv() * f, 1);
     }
 
     // pow
-    assert_eq!(Field(2).pow(3.into()), 8);
-    assert_eq!(Field(3).pow(9.into()), 19683);
-    assert_eq!(Field(51).pow(27.into()), 3760729523);
-    assert_eq!(Field(432).pow(0.into()), 1);
-    assert_eq!(Field(0).pow(123.into()), 0);
+    assert_eq!(Field::from(2).pow(3.into()), 8);
+    assert_eq!(Field::from(3).pow(9.into()), 19683);
+    assert_eq!(Field::from(51).pow(27.into()), 3760729523);
+    assert_eq!(Field::from(432).pow(0.into()), 1);
+    assert_eq!(Field::from(0).pow(123.into()), 0);
+}
+
+#[test]
+fn test_ord_compares_canonical_value() {
+    use rand::prelude::*;
+
+    // regression test: ordering must agree with the plain integer values,
+    // not the internal Montgomery representation (751702928 < 2223343850)
+    assert!(Field::from(751702928) < Field::from(2223343850));
+
+    let mut rng = thread_rng();
+    for _ in 0..100 {
+        let a = rng.gen::<u32>();
+        let b = rng.gen::<u32>();
+        assert_eq!(a < b, Field::from(a) < Field::from(b));
+    }
+}
+
+#[test]
+fn test_sqrt() {
+    use rand::prelude::*;
+
+    assert_eq!(Field::from(0).sqrt(), Some(Field::from(0)));
+    assert_eq!(Field::from(1).sqrt(), Some(Field::from(1)));
+
+    // squares have a square root that squares back to the original value
+    let mut rng = thread_rng();
+    for _ in 0..100 {
+        let x = Field::from(rng.gen::<u32>());
+        let square = x * x;
+        let root = square.sqrt().expect("a square must have a square root");
+        assert_eq!(root * root, square);
+    }
+
+    // GENERATOR is a quadratic non-residue by construction
+    assert_eq!(Field::from(GENERATOR).sqrt(), None);
+}
+
+#[test]
+fn test_batch_inv() {
+    use rand::prelude::*;
+
+    let mut rng = thread_rng();
+    let mut values: Vec<Field> = (0..100)
+        .map(|_| Field::from(rng.gen::<u32>()))
+        .collect();
+    // make sure a handful of zeros are exercised too
+    values[0] = Field::from(0);
+    values[42] = Field::from(0);
+
+    let expected: Vec<Field> = values.iter().map(|&v| v.inv()).collect();
+    batch_inv(&mut values);
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn test_byte_codec() {
+    // round trip
+    let f = Field::from(12345);
+    assert_eq!(Field::try_from_bytes(&f.into_bytes()).unwrap(), f);
+
+    // wrong length is rejected
+    assert_eq!(
+        Field::try_from_bytes(&[1, 2, 3]),
+        Err(FiniteFieldError::InputSizeMismatch)
+    );
+    assert_eq!(
+        Field::try_from_bytes(&[1, 2, 3, 4, 5]),
+        Err(FiniteFieldError::InputSizeMismatch)
+    );
+
+    // a non-canonical encoding (>= MODULUS) is rejected rather than reduced
+    assert_eq!(
+        Field::try_from_bytes(&MODULUS.to_le_bytes()),
+        Err(FiniteFieldError::ModulusOverflow)
+    );
+
+    // slice helpers round trip and validate length
+    let values: Vec<Field> = (0..10).map(Field::from).collect();
+    let bytes = serialize_field_vec(&values);
+    assert_eq!(deserialize_field_vec(&bytes).unwrap(), values);
+    assert_eq!(
+        deserialize_field_vec(&bytes[..bytes.len() - 1]),
+        Err(FiniteFieldError::InputSizeMismatch)
+    );
 }